@@ -1,16 +1,25 @@
-use crate::token::{Token, TokenType};
+use crate::token::{LosslessToken, Token, TokenType};
+
+#[cfg(test)]
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
 
 #[test]
 fn test_next_token() {
     const INPUT: &str = "let five = 5;";
 
-    let mut tests = Vec::new();
-    tests.push((TokenType::Let, "let"));
-    tests.push((TokenType::Ident, "five"));
-    tests.push((TokenType::Assign, "="));
-    tests.push((TokenType::Int, "5"));
-    tests.push((TokenType::Semicolon, ";"));
-    tests.push((TokenType::Eof, ""));
+    let tests = [
+        (TokenType::Let, "let"),
+        (TokenType::Ident, "five"),
+        (TokenType::Assign, "="),
+        (TokenType::Int, "5"),
+        (TokenType::Semicolon, ";"),
+        (TokenType::Eof, ""),
+    ];
 
     let mut l = Lexer::new(INPUT);
 
@@ -29,12 +38,90 @@ fn test_next_token() {
     }
 }
 
+#[cfg(test)]
+fn fixture_paths() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "mk"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no fixtures found in {:?}", dir);
+    paths
+}
+
+#[test]
+fn test_fixtures() {
+    let update = env::var_os("UPDATE_EXPECT").is_some();
+
+    for path in fixture_paths() {
+        let input = fs::read_to_string(&path).unwrap();
+        let mut l = Lexer::new(&input);
+
+        let mut actual = String::new();
+        loop {
+            let tok = l.next_token();
+            writeln!(actual, "{:?}", tok).unwrap();
+            if tok.token_type == TokenType::Eof {
+                break;
+            }
+        }
+
+        let expected_path = path.with_extension("tokens.expected");
+        if update {
+            fs::write(&expected_path, &actual).unwrap();
+            continue;
+        }
+
+        let expected = fs::read_to_string(&expected_path).unwrap_or_else(|_| {
+            panic!(
+                "missing {:?}, run with UPDATE_EXPECT=1 to create it",
+                expected_path
+            )
+        });
+        assert_eq!(
+            actual, expected,
+            "token dump mismatch for {:?}, run with UPDATE_EXPECT=1 to update",
+            path
+        );
+    }
+}
+
+#[test]
+fn test_lossless_round_trip() {
+    let mut inputs = vec![" \tlet  x =\r\n  \"a ${ b }  c\" ;\n\n".to_string()];
+    for path in fixture_paths() {
+        inputs.push(fs::read_to_string(&path).unwrap());
+    }
+
+    for input in inputs {
+        let mut l = Lexer::new(&input);
+
+        let mut output = String::new();
+        loop {
+            let tok = l.next_token_lossless();
+            output.push_str(&tok.leading_trivia);
+            output.push_str(&tok.text);
+            if tok.token.token_type == TokenType::Eof {
+                break;
+            }
+        }
+
+        assert_eq!(output, input, "lossless round trip changed the input");
+    }
+}
+
 #[derive(Debug)]
 pub struct Lexer {
     input: String,
     position: usize,
     read_position: usize,
     ch: char,
+    in_string: bool,
+    interpolation_pending: bool,
+    interpolations: Vec<usize>,
 }
 
 impl Lexer {
@@ -44,6 +131,9 @@ impl Lexer {
             position: usize::MIN,
             read_position: usize::MIN,
             ch: '\0',
+            in_string: false,
+            interpolation_pending: false,
+            interpolations: Vec::new(),
         };
         l.read_char();
         l
@@ -52,6 +142,18 @@ impl Lexer {
     pub fn next_token(&mut self) -> Token {
         let tok: Token;
 
+        if self.interpolation_pending {
+            self.interpolation_pending = false;
+            self.read_char();
+            self.read_char();
+            self.interpolations.push(0);
+            return Token::new(TokenType::InterpolationStart, "${");
+        }
+
+        if self.in_string {
+            return self.read_string();
+        }
+
         self.skip_whitespace();
 
         match self.ch {
@@ -79,19 +181,81 @@ impl Lexer {
             '-' => tok = Token::new(TokenType::Minus, &self.ch.to_string()),
             '/' => tok = Token::new(TokenType::Slash, &self.ch.to_string()),
             '*' => tok = Token::new(TokenType::Asterisk, &self.ch.to_string()),
-            '<' => tok = Token::new(TokenType::Lt, &self.ch.to_string()),
-            '>' => tok = Token::new(TokenType::Gt, &self.ch.to_string()),
+            '&' => tok = Token::new(TokenType::Ampersand, &self.ch.to_string()),
+            '|' => tok = Token::new(TokenType::Pipe, &self.ch.to_string()),
+            '^' => tok = Token::new(TokenType::Caret, &self.ch.to_string()),
+            '~' => tok = Token::new(TokenType::Tilde, &self.ch.to_string()),
+            '<' => {
+                if self.peek_char() == '<' {
+                    let ch = self.ch;
+                    self.read_char();
+                    let literal = ch.to_string() + &self.ch.to_string();
+                    tok = Token::new(TokenType::ShiftLeft, &literal);
+                } else {
+                    tok = Token::new(TokenType::Lt, &self.ch.to_string());
+                }
+            }
+            '>' => {
+                if self.peek_char() == '>' {
+                    let ch = self.ch;
+                    self.read_char();
+                    let literal = ch.to_string() + &self.ch.to_string();
+                    tok = Token::new(TokenType::ShiftRight, &literal);
+                } else {
+                    tok = Token::new(TokenType::Gt, &self.ch.to_string());
+                }
+            }
             '(' => tok = Token::new(TokenType::LParen, &self.ch.to_string()),
             ')' => tok = Token::new(TokenType::RParen, &self.ch.to_string()),
             ';' => tok = Token::new(TokenType::Semicolon, &self.ch.to_string()),
+            ':' => tok = Token::new(TokenType::Colon, &self.ch.to_string()),
+            '?' => {
+                if self.peek_char() == '?' {
+                    let ch = self.ch;
+                    self.read_char();
+                    let literal = ch.to_string() + &self.ch.to_string();
+                    tok = Token::new(TokenType::NullCoalesce, &literal);
+                } else {
+                    tok = Token::new(TokenType::Question, &self.ch.to_string());
+                }
+            }
             ',' => tok = Token::new(TokenType::Comma, &self.ch.to_string()),
-            '{' => tok = Token::new(TokenType::LBrace, &self.ch.to_string()),
-            '}' => tok = Token::new(TokenType::RBrace, &self.ch.to_string()),
-            '\0' => tok = Token::new(TokenType::Eof, &"".to_string()),
+            '.' => {
+                if self.input[self.position..].starts_with("...") {
+                    self.read_char();
+                    self.read_char();
+                    tok = Token::new(TokenType::Ellipsis, "...");
+                } else {
+                    tok = Token::new(TokenType::Dot, &self.ch.to_string());
+                }
+            }
+            '{' => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                tok = Token::new(TokenType::LBrace, &self.ch.to_string());
+            }
+            '}' => {
+                if self.interpolations.last() == Some(&0) {
+                    self.interpolations.pop();
+                    self.in_string = true;
+                    tok = Token::new(TokenType::InterpolationEnd, &self.ch.to_string());
+                } else {
+                    if let Some(depth) = self.interpolations.last_mut() {
+                        *depth -= 1;
+                    }
+                    tok = Token::new(TokenType::RBrace, &self.ch.to_string());
+                }
+            }
+            '"' => {
+                self.read_char();
+                return self.read_string();
+            }
+            '\0' => tok = Token::new(TokenType::Eof, ""),
             _ => {
                 if Lexer::is_letter(self.ch) {
                     let literal = self.read_identifier();
-                    let token_type = Token::lookup_ident(&literal);
+                    let token_type = Token::lookup_ident(literal);
                     tok = Token::new(token_type, literal);
                     return tok;
                 } else if Lexer::is_digit(self.ch) {
@@ -108,15 +272,39 @@ impl Lexer {
         tok
     }
 
+    // Like next_token, but also returns the whitespace (and shebang line)
+    // before the token and the exact source text it was lexed from, so
+    // concatenating both for every token up to Eof reproduces the input.
+    #[allow(dead_code)]
+    pub fn next_token_lossless(&mut self) -> LosslessToken {
+        let trivia_start = self.offset();
+        if !self.in_string && !self.interpolation_pending {
+            self.skip_whitespace();
+        }
+        let token_start = self.offset();
+        let token = self.next_token();
+        let token_end = self.offset();
+
+        LosslessToken {
+            token,
+            leading_trivia: self.input[trivia_start..token_start].to_string(),
+            text: self.input[token_start..token_end].to_string(),
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.position.min(self.input.len())
+    }
+
     fn read_char(&mut self) {
         if self.read_position >= self.input.len() {
             self.ch = '\0';
         } else {
-            let ch = self.input.chars().nth(self.read_position).unwrap();
+            let ch = self.input[self.read_position..].chars().next().unwrap();
             self.ch = ch;
         }
         self.position = self.read_position;
-        self.read_position += 1
+        self.read_position += self.ch.len_utf8()
     }
 
     fn read_identifier(&mut self) -> &str {
@@ -135,23 +323,49 @@ impl Lexer {
         &self.input[position..self.position]
     }
 
+    // Reads one literal segment of a string. A segment ends at the closing
+    // quote or right before `${`, in which case the next call to next_token
+    // emits InterpolationStart and lexes the embedded expression.
+    fn read_string(&mut self) -> Token {
+        let mut literal = String::new();
+        while self.ch != '"' && self.ch != '\0' {
+            if self.ch == '$' && self.peek_char() == '{' {
+                self.in_string = false;
+                self.interpolation_pending = true;
+                return Token::new(TokenType::String, &literal);
+            }
+            literal.push(self.ch);
+            self.read_char();
+        }
+
+        self.in_string = false;
+        self.read_char();
+        Token::new(TokenType::String, &literal)
+    }
+
     fn peek_char(&mut self) -> char {
         if self.read_position >= self.input.len() {
             '\0'
         } else {
-            self.input.chars().nth(self.read_position).unwrap()
+            self.input[self.read_position..].chars().next().unwrap()
         }
     }
 
     fn is_digit(ch: char) -> bool {
-        '0' <= ch && ch <= '9'
+        ch.is_ascii_digit()
     }
 
     fn is_letter(ch: char) -> bool {
-        'a' <= ch && ch <= 'z' || 'A' <= ch && ch <= 'Z' || ch == '_'
+        ch.is_ascii_alphabetic() || ch == '_'
     }
 
     fn skip_whitespace(&mut self) {
+        if self.position == 0 && self.input.starts_with("#!") {
+            while self.ch != '\n' && self.ch != '\0' {
+                self.read_char();
+            }
+        }
+
         while self.ch == ' ' || self.ch == '\t' || self.ch == '\n' || self.ch == '\r' {
             self.read_char();
         }
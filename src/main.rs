@@ -4,17 +4,18 @@ mod lexer;
 mod repl;
 mod token;
 
+use repl::ReplConfig;
+
 fn main() {
     let uid = get_current_uid();
 
     // UID를 이용해 유저 정보를 얻습니다.
     if let Some(user) = get_user_by_uid(uid) {
-        println!(
-            "\nHello {}! This is the Monkey programming language!",
+        let banner = format!(
+            "\nHello {}! This is the Monkey programming language!\nFeel free to type in commands",
             user.name().to_string_lossy()
         );
-        println!("Feel free to type in commands");
-        repl::start();
+        repl::start(ReplConfig::new().banner(&banner));
     } else {
         panic!()
     }
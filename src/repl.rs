@@ -1,14 +1,49 @@
 use std::process;
 
-use rustyline::DefaultEditor;
+use rustyline::{error::ReadlineError, DefaultEditor};
 
 use crate::{lexer::Lexer, token::TokenType};
 
-pub fn start() {
+pub struct ReplConfig {
+    prompt: String,
+    banner: Option<String>,
+}
+
+impl Default for ReplConfig {
+    fn default() -> Self {
+        ReplConfig {
+            prompt: ">> ".to_string(),
+            banner: None,
+        }
+    }
+}
+
+impl ReplConfig {
+    pub fn new() -> Self {
+        ReplConfig::default()
+    }
+
+    #[allow(dead_code)]
+    pub fn prompt(mut self, prompt: &str) -> Self {
+        self.prompt = prompt.to_string();
+        self
+    }
+
+    pub fn banner(mut self, banner: &str) -> Self {
+        self.banner = Some(banner.to_string());
+        self
+    }
+}
+
+pub fn start(config: ReplConfig) {
+    if let Some(banner) = &config.banner {
+        println!("{}", banner);
+    }
+
     let mut rl = DefaultEditor::new().unwrap();
 
     loop {
-        let readline = rl.readline(">> ");
+        let readline = rl.readline(&config.prompt);
         match readline {
             Ok(line) => {
                 let mut l = Lexer::new(&line);
@@ -21,6 +56,7 @@ pub fn start() {
                     println!("{:?}", tok);
                 }
             }
+            Err(ReadlineError::Interrupted) => continue,
             Err(_) => {
                 process::exit(0);
             }
@@ -7,14 +7,27 @@ pub enum TokenType {
     Eof,
     Ident,
     Int,
+    String,
+    InterpolationStart,
+    InterpolationEnd,
     Assign,
     Plus,
     Minus,
     Bang,
     Asterisk,
     Slash,
+    Ampersand,
+    Pipe,
+    Caret,
+    Tilde,
+    ShiftLeft,
+    ShiftRight,
     Comma,
+    Dot,
+    Ellipsis,
     Semicolon,
+    Colon,
+    Question,
     Lt,
     Gt,
     LParen,
@@ -25,11 +38,13 @@ pub enum TokenType {
     Let,
     True,
     False,
+    Null,
     If,
     Else,
     Return,
     Eq,
     NotEq,
+    NullCoalesce,
 }
 
 static KEYWORDS: Lazy<HashMap<&'static str, TokenType>> = Lazy::new(|| {
@@ -38,6 +53,7 @@ static KEYWORDS: Lazy<HashMap<&'static str, TokenType>> = Lazy::new(|| {
     m.insert("let", TokenType::Let);
     m.insert("true", TokenType::True);
     m.insert("false", TokenType::False);
+    m.insert("null", TokenType::Null);
     m.insert("if", TokenType::If);
     m.insert("else", TokenType::Else);
     m.insert("return", TokenType::Return);
@@ -47,6 +63,7 @@ static KEYWORDS: Lazy<HashMap<&'static str, TokenType>> = Lazy::new(|| {
 #[derive(Debug)]
 pub struct Token {
     pub token_type: TokenType,
+    #[allow(dead_code)]
     pub literal: String,
 }
 
@@ -67,3 +84,11 @@ impl Token {
         }
     }
 }
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct LosslessToken {
+    pub token: Token,
+    pub leading_trivia: String,
+    pub text: String,
+}
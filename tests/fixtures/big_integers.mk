@@ -0,0 +1,3 @@
+let max = 9223372036854775807;
+let beyond = 170141183460469231731687303715884105728;
+let factorial = 265252859812191058636308480000000;
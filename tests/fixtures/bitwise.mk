@@ -0,0 +1,3 @@
+let mask = (flags & 255) | (1 << 4);
+let toggled = ~mask ^ 15 >> 2;
+a < b > c;
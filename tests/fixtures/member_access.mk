@@ -0,0 +1,3 @@
+let name = person.name;
+person.address.city = "Seoul";
+f(...args).len;
@@ -0,0 +1,2 @@
+let nothing = null;
+if (value == null) { return nullable; }
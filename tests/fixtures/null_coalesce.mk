@@ -0,0 +1,2 @@
+let port = config ?? 8080;
+let x = a ?? b ? c : d;
@@ -0,0 +1,2 @@
+let sum = fn(first, ...rest) { first };
+sum(...numbers);
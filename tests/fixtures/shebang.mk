@@ -0,0 +1,2 @@
+#!/usr/bin/env monkey
+let x = 5;
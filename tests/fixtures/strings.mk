@@ -0,0 +1,5 @@
+let greeting = "hello world";
+let message = "sum is ${a + b}!";
+"${name}";
+"outer ${ "inner ${x}" } done";
+"${ fn(x) { x }(1) }";
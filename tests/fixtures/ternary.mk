@@ -0,0 +1 @@
+let max = a > b ? a : b;
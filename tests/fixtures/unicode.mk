@@ -0,0 +1,3 @@
+let greeting = "안녕하세요 🐵";
+let s = "${name}님, 🎉 ${count}개";
+let 🍌 = 1;